@@ -1,5 +1,88 @@
 use crate::*;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
 
+/// The raw, untyped index backing an [`Idx<T>`].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct RawIdx(u32);
+
+impl RawIdx {
+    pub fn from_u32(v: u32) -> Self {
+        Self(v)
+    }
+
+    pub fn into_u32(self) -> u32 {
+        self.0
+    }
+}
+
+impl From<u32> for RawIdx {
+    fn from(v: u32) -> Self {
+        Self::from_u32(v)
+    }
+}
+
+impl From<RawIdx> for u32 {
+    fn from(raw: RawIdx) -> u32 {
+        raw.into_u32()
+    }
+}
+
+/// A typed, stable index into an [`Arena<T>`].
+///
+/// Unlike a pointer or a `Weak<T>`, an `Idx<T>` never needs to be
+/// "upgraded": as long as the arena that produced it is alive, indexing
+/// with it always succeeds, because the arena never frees entries.
+pub struct Idx<T> {
+    raw: RawIdx,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Idx<T> {
+    fn from_raw(raw: RawIdx) -> Self {
+        Self { raw, _marker: PhantomData }
+    }
+
+    pub fn into_raw(self) -> RawIdx {
+        self.raw
+    }
+}
+
+impl<T> Clone for Idx<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Idx<T> {}
+
+impl<T> PartialEq for Idx<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.raw == other.raw
+    }
+}
+
+impl<T> Eq for Idx<T> {}
+
+impl<T> Hash for Idx<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.raw.hash(state)
+    }
+}
+
+impl<T> std::fmt::Debug for Idx<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Idx").field(&self.raw.into_u32()).finish()
+    }
+}
+
+/// An append-only arena, modeled after rust-analyzer's `ra_arena`.
+///
+/// Entries are stored by value (behind an `Rc` so that handing out an
+/// [`Idx<T>`] never needs to move or invalidate a previously allocated
+/// entry) and are looked up in O(1) through [`Arena::get`]/[`Arena::get_mut`].
+/// The arena never frees an entry, so a plain, non-generational index is
+/// enough to keep handles stable and safe to copy around.
 pub struct Arena<T> {
     data: RefCell<Vec<Rc<T>>>,
 }
@@ -11,72 +94,103 @@ impl<T> Arena<T> {
         }
     }
 
-    pub fn allocate(&self, value: T) -> Weak<T> {
-        let obj = Rc::new(value);
-        self.data.borrow_mut().push(obj.clone());
-        Rc::downgrade(&obj)
+    pub fn allocate(&self, value: T) -> Idx<T> {
+        let mut data = self.data.borrow_mut();
+        let raw = RawIdx::from_u32(data.len() as u32);
+        data.push(Rc::new(value));
+        Idx::from_raw(raw)
+    }
+
+    /// Looks up an entry, cloning the `Rc<T>` handle out from behind the
+    /// `RefCell` borrow. A later `allocate()` may reallocate the backing
+    /// `Vec`, so a reference borrowed straight out of it could not
+    /// outlive the borrow guard; handing back an owned, cheaply-cloned
+    /// `Rc<T>` instead sidesteps that without any `unsafe`.
+    pub fn get(&self, index: Idx<T>) -> Rc<T> {
+        self.data.borrow()[index.into_raw().into_u32() as usize].clone()
+    }
+
+    /// Mutable counterpart to [`Arena::get`]. Takes `&mut self`, so the
+    /// borrow checker (not a `RefCell` guard) is what proves exclusive
+    /// access here — no `unsafe` needed either way.
+    pub fn get_mut(&mut self, index: Idx<T>) -> &mut Rc<T> {
+        &mut self.data.get_mut()[index.into_raw().into_u32() as usize]
     }
 }
 
+thread_local! {
+    static SYMBOL_ARENAS: RefCell<Vec<Rc<Arena<Symbol1>>>> = RefCell::new(Vec::new());
+}
+
+fn register_symbol_arena(arena: Rc<Arena<Symbol1>>) -> u32 {
+    SYMBOL_ARENAS.with(|arenas| {
+        let mut arenas = arenas.borrow_mut();
+        let id = arenas.len() as u32;
+        arenas.push(arena);
+        id
+    })
+}
+
 pub struct LmtFactory {
-    arena: Arena<Symbol1>,
+    arena_id: u32,
+    arena: Rc<Arena<Symbol1>>,
 }
 
 impl LmtFactory {
     pub fn new() -> Self {
-        Self {
-            arena: Arena::new(),
-        }
+        let arena = Rc::new(Arena::new());
+        let arena_id = register_symbol_arena(arena.clone());
+        Self { arena_id, arena }
     }
 
     pub fn create_meaning_slot(&self, name: String) -> Symbol {
-        Symbol(self.arena.allocate(Symbol1::MeaningSlot(Rc::new(MeaningSlot1 {
+        let idx = self.arena.allocate(Symbol1::MeaningSlot(Rc::new(MeaningSlot1 {
             name,
             inherits: RefCell::new(None),
             submeanings: shared_array![],
             methods: shared_map![],
-        }))))
+            attrs: RefCell::new(HashMap::new()),
+        })));
+        Symbol { arena: self.arena_id, idx }
     }
 
     pub fn create_field_slot(&self, is_ref: bool, name: String, field_type: syn::Type, field_init: syn::Expr) -> Symbol {
-        Symbol(self.arena.allocate(Symbol1::FieldSlot(Rc::new(FieldSlot1 {
+        let idx = self.arena.allocate(Symbol1::FieldSlot(Rc::new(FieldSlot1 {
             is_ref,
             name,
             field_type,
             field_init,
-        }))))
+            attrs: RefCell::new(HashMap::new()),
+        })));
+        Symbol { arena: self.arena_id, idx }
     }
 
     pub fn create_method_slot(&self, name: String, defined_in: Symbol, doc_attribute: Option<syn::Attribute>) -> Symbol {
-        Symbol(self.arena.allocate(Symbol1::MethodSlot(Rc::new(MethodSlot1 {
+        let idx = self.arena.allocate(Symbol1::MethodSlot(Rc::new(MethodSlot1 {
             name,
             defined_in,
             doc_attribute,
             override_logic_mapping: SharedMap::new(),
-        }))))
-    }
-}
-
-#[derive(Clone)]
-pub struct Symbol(Weak<Symbol1>);
-
-impl Eq for Symbol {}
-
-impl PartialEq for Symbol {
-    fn eq(&self, other: &Self) -> bool {
-        self.0.ptr_eq(&other.0)
+            attrs: RefCell::new(HashMap::new()),
+        })));
+        Symbol { arena: self.arena_id, idx }
     }
 }
 
-impl Hash for Symbol {
-    /// Performs hashing of the symbol by reference.
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.0.as_ptr().hash(state)
-    }
+/// A small, `Copy` handle to a [`Symbol1`] living in some [`LmtFactory`]'s
+/// arena: an arena id paired with a stable [`Idx<Symbol1>`]. `Eq`/`Hash`
+/// fall out of the index instead of pointer identity, so handles survive
+/// across passes and can be used as map keys without any upgrade step.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Symbol {
+    arena: u32,
+    idx: Idx<Symbol1>,
 }
 
 macro access {
-    ($symbol:expr) => { $symbol.0.upgrade().unwrap().as_ref() },
+    ($symbol:expr) => {
+        SYMBOL_ARENAS.with(|arenas| arenas.borrow()[$symbol.arena as usize].clone()).get($symbol.idx).as_ref()
+    },
 }
 
 impl Symbol {
@@ -172,6 +286,35 @@ impl Symbol {
             _ => panic!(),
         }
     }
+
+    /// Retrieves an attribute previously stored through [`Symbol::set_attr`]
+    /// under key `K`, or `None` if no pass has attached one yet.
+    pub fn get_attr<K: Key>(&self) -> Option<Rc<K::Value>> {
+        access!(self).attrs().borrow().get(&TypeId::of::<K>()).map(|v| {
+            v.downcast_ref::<Rc<K::Value>>().unwrap().clone()
+        })
+    }
+
+    /// Attaches an attribute to this symbol under key `K`, overwriting any
+    /// value previously stored under the same key.
+    pub fn set_attr<K: Key>(&self, value: K::Value) {
+        access!(self).attrs().borrow_mut().insert(TypeId::of::<K>(), Box::new(Rc::new(value)));
+    }
+}
+
+/// A zero-sized, user-declared marker type identifying an entry in a
+/// [`Symbol`]'s attribute store, modeled after rust-analyzer's `dyn_map`.
+/// Different passes declare their own `Key` types, so two passes can
+/// never collide even if they happen to store the same `Value` type.
+///
+/// ```ignore
+/// struct ResolvedType;
+/// impl Key for ResolvedType {
+///     type Value = Ty;
+/// }
+/// ```
+pub trait Key: 'static {
+    type Value: 'static;
 }
 
 impl ToString for Symbol {
@@ -186,11 +329,24 @@ enum Symbol1 {
     MethodSlot(Rc<MethodSlot1>),
 }
 
+impl Symbol1 {
+    /// Returns this symbol's attribute store, regardless of which slot
+    /// kind it is.
+    fn attrs(&self) -> &RefCell<HashMap<TypeId, Box<dyn Any>>> {
+        match self {
+            Symbol1::MeaningSlot(slot) => &slot.attrs,
+            Symbol1::FieldSlot(slot) => &slot.attrs,
+            Symbol1::MethodSlot(slot) => &slot.attrs,
+        }
+    }
+}
+
 struct MeaningSlot1 {
     name: String,
     inherits: RefCell<Option<Symbol>>,
     submeanings: SharedArray<Symbol>,
     methods: SharedMap<String, Symbol>,
+    attrs: RefCell<HashMap<TypeId, Box<dyn Any>>>,
 }
 
 struct FieldSlot1 {
@@ -198,6 +354,7 @@ struct FieldSlot1 {
     field_type: syn::Type,
     field_init: syn::Expr,
     is_ref: bool,
+    attrs: RefCell<HashMap<TypeId, Box<dyn Any>>>,
 }
 
 struct MethodSlot1 {
@@ -205,6 +362,7 @@ struct MethodSlot1 {
     defined_in: Symbol,
     doc_attribute: Option<syn::Attribute>,
     override_logic_mapping: SharedMap<Symbol, Rc<OverrideLogicMapping>>,
+    attrs: RefCell<HashMap<TypeId, Box<dyn Any>>>,
 }
 
 pub struct OverrideLogicMapping {
@@ -237,16 +395,17 @@ impl OverrideLogicMapping {
 }
 
 /// A meaning slot.
-/// 
+///
 /// # Supported methods
-/// 
+///
 /// * `is_meaning_slot()` — Returns `true`.
 /// * `name()`
 /// * `inherits()`
 /// * `set_inherits()`
 /// * `submeanings()`
 /// * `methods()`
-#[derive(Clone, Hash, PartialEq, Eq)]
+/// * `get_attr::<K>()` / `set_attr::<K>()` — Typed attribute storage.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
 pub struct MeaningSlot(pub Symbol);
 
 impl Deref for MeaningSlot {
@@ -258,15 +417,16 @@ impl Deref for MeaningSlot {
 }
 
 /// A field slot.
-/// 
+///
 /// # Supported methods
-/// 
+///
 /// * `is_field_slot()` — Returns `true`.
 /// * `is_ref()`
 /// * `name()`
 /// * `field_type()`
 /// * `field_init()`
-#[derive(Clone, Hash, PartialEq, Eq)]
+/// * `get_attr::<K>()` / `set_attr::<K>()` — Typed attribute storage.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
 pub struct FieldSlot(pub Symbol);
 
 impl Deref for FieldSlot {
@@ -278,15 +438,16 @@ impl Deref for FieldSlot {
 }
 
 /// A method slot.
-/// 
+///
 /// # Supported methods
-/// 
+///
 /// * `is_method_slot()` — Returns `true`.
 /// * `name()`
 /// * `defined_in()`
 /// * `doc_attribute()`
 /// * `override_logic_mapping()` — Mapping from submeaning slot to override logic.
-#[derive(Clone, Hash, PartialEq, Eq)]
+/// * `get_attr::<K>()` / `set_attr::<K>()` — Typed attribute storage.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
 pub struct MethodSlot(pub Symbol);
 
 impl Deref for MethodSlot {
@@ -295,4 +456,4 @@ impl Deref for MethodSlot {
         assert!(self.0.is_method_slot());
         &self.0
     }
-}
\ No newline at end of file
+}
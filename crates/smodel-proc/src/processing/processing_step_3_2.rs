@@ -15,7 +15,11 @@ impl ProcessingStep3_2 {
             meaning.fields().set(slot.name(), slot.clone());
         }
 
-        // 3. Contribute a field to the #DATA::M structure.
+        // 3. Contribute a field to the #DATA::M structure. Under
+        // `#[smodel(serialize)]` the struct is additionally derived with
+        // `serde::Serialize`/`Deserialize`; `Cell<T>`/`RefCell<T>` already
+        // round-trip through their inner `T` via serde's own impls, so no
+        // extra per-field annotation is needed here.
         let field_name = slot.name();
         let field_name_id = Ident::new(&field_name, Span::call_site());
         let field_type = slot.field_type();
@@ -36,31 +40,76 @@ impl ProcessingStep3_2 {
     fn define_accessors(&self, _host: &mut SModelHost, meaning: &Symbol, slot: &Symbol, field_name: &str, field_type: &Type, base_accessor: &str, asc_meaning_list: &[Symbol]) {
         let getter_name = Ident::new(&field_name, Span::call_site());
         let setter_name = Ident::new(&format!("set_{}", field_name), Span::call_site());
-        let fv = proc_macro2::TokenStream::from_str(&self.match_field(asc_meaning_list, 0, &format!("{base_accessor}.upgrade().unwrap()"), field_name)).unwrap();
+        let field_name_id = Ident::new(field_name, Span::call_site());
+
+        // The field's defining layer (`meaning` itself, the last entry of
+        // `asc_meaning_list`) is cached as a raw pointer by `M::new`
+        // (`ProcessingStep3_7::cache_layer_pointers`), except when `meaning`
+        // is the root, whose layer is never cached. Reuse the cached pointer
+        // instead of re-walking the `#DATA_VARIANT_FIELD` chain on every
+        // access; the walk is kept as a debug-assert so a stale cache is
+        // caught in debug builds, and is what runs when there is no cache
+        // entry.
+        let data_id = Ident::new(DATA, Span::call_site());
+        let meaning_name_id = Ident::new(&meaning.name(), Span::call_site());
+        let layer_walk = proc_macro2::TokenStream::from_str(&self.match_layer(asc_meaning_list, 0, "__root", false)).unwrap();
+        let root_accessor = proc_macro2::TokenStream::from_str(&format!("{base_accessor}.upgrade().unwrap()")).unwrap();
+
+        // `__root` is bound once up front so every reference below borrows
+        // from that local `Rc` instead of re-deriving it from `.upgrade()`
+        // inside the `match` arms — an arm-local upgrade would be a
+        // temporary dropped at the end of the `match` expression, and
+        // `__layer` would then dangle once borrowed out of it. The field
+        // operation `op` runs inside the block, so no borrow of `__root`
+        // outlives it.
+        let fv = |op: proc_macro2::TokenStream| quote! {
+            {
+                let __root = #root_accessor;
+                let __cached = __root.__layer_cache.borrow()
+                    .get(&::std::any::TypeId::of::<#data_id::#meaning_name_id>()).copied();
+                let __layer: &#data_id::#meaning_name_id = match __cached {
+                    Some(__p) => unsafe {
+                        let __p = __p as *const #data_id::#meaning_name_id;
+                        debug_assert_eq!(__p, #layer_walk as *const #data_id::#meaning_name_id,
+                            "cached layer pointer is out of sync with the live variant");
+                        &*__p
+                    },
+                    None => #layer_walk,
+                };
+                let __out = __layer.#field_name_id #op;
+                __out
+            }
+        };
 
         if slot.is_ref() {
+            let (get, set) = (fv(quote! { .borrow().clone() }), fv(quote! { .replace(v) }));
             meaning.method_output().borrow_mut().extend(quote! {
                 fn #getter_name(&self) -> #field_type {
-                    #fv.borrow().clone()
+                    #get
                 }
                 fn #setter_name(&self, v: #field_type) {
-                    #fv.replace(v);
+                    #set;
                 }
             });
         } else {
+            let (get, set) = (fv(quote! { .get() }), fv(quote! { .set(v) }));
             meaning.method_output().borrow_mut().extend(quote! {
                 fn #getter_name(&self) -> #field_type {
-                    #fv.get()
+                    #get
                 }
 
                 fn #setter_name(&self, v: #field_type) {
-                    #fv.set(v);
+                    #set;
                 }
             });
         }
     }
 
     /// Matches a field. `base` is assumed to be a `Rc<#DATA::M>` value.
+    ///
+    /// No longer called: `define_accessors` now goes through
+    /// [`Self::match_layer`] instead.
+    #[allow(dead_code)]
     fn match_field(&self, asc_meaning_list: &[Symbol], meaning_index: usize, base: &str, field_name: &str) -> String {
         let inherited = if asc_meaning_list.len() - meaning_index == 1 {
             None
@@ -72,9 +121,32 @@ impl ProcessingStep3_2 {
         let Some(inherited) = meaning.inherits() else {
             return format!("{}.{}", base, field_name);
         };
-        format!("(if {DATA}::{}::{}(o) = &{base}.{DATA_VARIANT_FIELD} {{ {} }} else {{ panic!() }})",
+        format!("(if let {DATA}::{}::{}(o) = &{base}.{DATA_VARIANT_FIELD} {{ {} }} else {{ panic!() }})",
             DATA_VARIANT_PREFIX.to_owned() + &inherited.name(),
             meaning.name(),
             self.match_field(asc_meaning_list, meaning_index + 1, "o", field_name))
     }
+
+    /// Walks down to a `&#DATA::Mi` struct reference (rather than
+    /// projecting into one of its fields), peeling one variant layer per
+    /// entry of `asc_meaning_list` after the first, as
+    /// [`Self::match_field`] does. Used as the debug-assert and fallback walk
+    /// here; `ProcessingStep3_7` has its own copy for the pointer-capture
+    /// walk.
+    fn match_layer(&self, asc_meaning_list: &[Symbol], meaning_index: usize, base: &str, base_is_ref: bool) -> String {
+        let inherited = if asc_meaning_list.len() - meaning_index == 1 {
+            None
+        } else {
+            Some(asc_meaning_list[meaning_index].clone())
+        };
+        let meaning = asc_meaning_list[meaning_index + if inherited.is_some() { 1 } else { 0 }].clone();
+
+        let Some(inherited) = inherited else {
+            return if base_is_ref { format!("(&**{base})") } else { format!("(&*{base})") };
+        };
+        format!("(if let {DATA}::{}::{}(o) = &{base}.{DATA_VARIANT_FIELD} {{ {} }} else {{ panic!() }})",
+            DATA_VARIANT_PREFIX.to_owned() + &inherited.name(),
+            meaning.name(),
+            self.match_layer(asc_meaning_list, meaning_index + 1, "o", true))
+    }
 }
\ No newline at end of file
@@ -0,0 +1,114 @@
+use crate::*;
+
+/// Emits safe downcasting for a meaning hierarchy: `is_<Sub>`/`to_<Sub>`
+/// accessor methods for each direct submeaning, plus a
+/// `match_submeaning_<M>!` macro expanded into a real Rust `match` over
+/// the generated `#DATA::#DATA_VARIANT_PREFIX<M>` enum. Every direct
+/// submeaning needs its own arm — the macro's matcher is what enforces
+/// that, since a missing arm just fails to parse against it; the
+/// emitted `match` is exhaustive by construction. The trailing `_ => ...` wildcard
+/// is narrower than a normal `match` catch-all: it only stands in for
+/// the "no submeaning" (leaf) case, never for an omitted submeaning.
+pub struct ProcessingStep3_3();
+
+impl ProcessingStep3_3 {
+    pub fn exec(&self, _host: &mut SModelHost, meaning: &Symbol, base_accessor: &str) {
+        let submeanings = meaning.submeanings();
+        if submeanings.borrow().is_empty() {
+            return;
+        }
+
+        for sub in submeanings.borrow().iter() {
+            self.define_downcast(meaning, sub, base_accessor);
+        }
+
+        self.define_match_submeaning_macro(meaning, &submeanings, base_accessor);
+    }
+
+    /// Emits `is_<Sub>`/`to_<Sub>` for a single direct submeaning, peeling
+    /// exactly one `#DATA_VARIANT_FIELD` layer and returning `None`
+    /// instead of panicking when the live variant isn't `sub`.
+    fn define_downcast(&self, meaning: &Symbol, sub: &Symbol, base_accessor: &str) {
+        let meaning_name = meaning.name();
+        let sub_name = sub.name();
+        let sub_name_id = Ident::new(&sub_name, Span::call_site());
+        let is_name = Ident::new(&format!("is_{}", sub_name), Span::call_site());
+        let to_name = Ident::new(&format!("to_{}", sub_name), Span::call_site());
+        let variant = DATA_VARIANT_PREFIX.to_owned() + &meaning_name;
+
+        let is_probe = proc_macro2::TokenStream::from_str(&format!(
+            "matches!(&{base_accessor}.upgrade().unwrap().{DATA_VARIANT_FIELD}, {DATA}::{variant}::{sub_name}(_))"
+        )).unwrap();
+        let to_probe = proc_macro2::TokenStream::from_str(&format!(
+            "if let {DATA}::{variant}::{sub_name}(_) = &{base_accessor}.upgrade().unwrap().{DATA_VARIANT_FIELD} {{ Some({sub_name}(self.clone())) }} else {{ None }}"
+        )).unwrap();
+
+        meaning.method_output().borrow_mut().extend(quote! {
+            fn #is_name(&self) -> bool {
+                #is_probe
+            }
+
+            fn #to_name(&self) -> Option<#sub_name_id> {
+                #to_probe
+            }
+        });
+    }
+
+    /// Emits a `match_submeaning_<M>!` macro. Its matcher requires one
+    /// arm per direct submeaning, named and in declaration order — it
+    /// is not a free-form `match`, so submeanings can't be reordered or
+    /// left out in favor of the wildcard. The optional trailing
+    /// `_ => ...` only covers `#DATA_VARIANT_NO_SUBMEANING` (`meaning`
+    /// itself, not downcast to any submeaning); each arm's value is
+    /// rebound through the matching `to_<Sub>()` accessor.
+    fn define_match_submeaning_macro(&self, meaning: &Symbol, submeanings: &SharedArray<Symbol>, base_accessor: &str) {
+        let meaning_name = meaning.name();
+        let macro_name = Ident::new(&format!("match_submeaning_{}", meaning_name), Span::call_site());
+        let variant = Ident::new(&(DATA_VARIANT_PREFIX.to_owned() + &meaning_name), Span::call_site());
+        let no_submeaning = Ident::new(DATA_VARIANT_NO_SUBMEANING, Span::call_site());
+        let data_id = Ident::new(DATA, Span::call_site());
+        let scrutinee = proc_macro2::TokenStream::from_str(&format!(
+            "{}.upgrade().unwrap().{DATA_VARIANT_FIELD}", base_accessor.replacen("self", "$value", 1)
+        )).unwrap();
+
+        let mut arm_patterns = TokenStream::new();
+        let mut match_arms = TokenStream::new();
+        for sub in submeanings.borrow().iter() {
+            let sub_name = sub.name();
+            let sub_name_id = Ident::new(&sub_name, Span::call_site());
+            let to_name = Ident::new(&format!("to_{}", sub_name), Span::call_site());
+            let v_id = proc_macro2::TokenStream::from_str(&format!("$v_{sub_name}:pat")).unwrap();
+            let v_use = proc_macro2::TokenStream::from_str(&format!("$v_{sub_name}")).unwrap();
+            let b_id = proc_macro2::TokenStream::from_str(&format!("$b_{sub_name}:expr")).unwrap();
+            let b_use = proc_macro2::TokenStream::from_str(&format!("$b_{sub_name}")).unwrap();
+            arm_patterns.extend(quote! {
+                #sub_name_id(#v_id) => #b_id,
+            });
+            match_arms.extend(quote! {
+                #data_id::#variant::#sub_name_id(_) => {
+                    let #v_use = $value.#to_name().unwrap();
+                    #b_use
+                }
+            });
+        }
+
+        // `macro_rules!` isn't a valid associated item, so it can't be
+        // spliced into `impl M { .. }` alongside the accessors above —
+        // it goes out to module scope instead, exported so downstream
+        // crates can name it the same way they'd name any other
+        // generated item.
+        meaning.module_output().borrow_mut().extend(quote! {
+            #[macro_export]
+            macro_rules! #macro_name {
+                ($value:expr, { #arm_patterns $(_ => $wildcard:expr $(,)?)? }) => {
+                    match &#scrutinee {
+                        #match_arms
+                        #data_id::#variant::#no_submeaning => {
+                            $($wildcard)?
+                        }
+                    }
+                };
+            }
+        });
+    }
+}
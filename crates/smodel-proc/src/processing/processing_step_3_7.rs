@@ -6,7 +6,7 @@ pub struct ProcessingStep3_7();
 
 impl ProcessingStep3_7 {
     // Define the constructor
-    pub fn exec(&self, _host: &mut SModelHost, node: Option<&MeaningConstructor>, meaning: &Symbol, asc_meaning_list: &[Symbol], arena_type_name: &str) {
+    pub fn exec(&self, _host: &mut SModelHost, node: Option<&MeaningConstructor>, meaning: &Symbol, asc_meaning_list: &[Symbol], arena_type_name: &str, serialize: bool) {
         let input = node.map(|node| node.inputs.clone()).unwrap_or(Punctuated::new());
         let type_params = node.map(|node| [node.generics.lt_token.to_token_stream(), node.generics.params.to_token_stream(), node.generics.gt_token.to_token_stream()]).unwrap_or([
             proc_macro2::TokenStream::new(),
@@ -20,6 +20,38 @@ impl ProcessingStep3_7 {
         let ctor_init_name_id = Ident::new(CTOR_INIT_NAME, Span::call_site());
         let arena_type_name_id = Ident::new(arena_type_name, Span::call_site());
 
+        // `exec` runs exactly once per meaning (unlike `ProcessingStep3_2::exec`,
+        // which runs once per field), so unlike that pass there's no
+        // duplicate-emission risk in deriving serde here unconditionally.
+        // Covers both the `#DATA::M` struct and its `#DATA_VARIANT_PREFIX`
+        // variant enum, which every meaning has regardless of whether it
+        // declares submeanings (see `init_data`'s unconditional use of it).
+        if serialize {
+            meaning.struct_output().borrow_mut().extend(quote! {
+                #[derive(::serde::Serialize, ::serde::Deserialize)]
+            });
+            meaning.enum_output().borrow_mut().extend(quote! {
+                #[derive(::serde::Serialize, ::serde::Deserialize)]
+            });
+        }
+
+        // The root struct's pointer cache (populated below by
+        // `cache_layer_pointers`) holds raw pointers and `TypeId`s,
+        // neither of which is `Serialize`/`Deserialize` — and it's pure
+        // derived data besides, so it should never round-trip through
+        // serde in the first place.
+        if asc_meaning_list.len() == 1 {
+            let skip_attr = if serialize {
+                quote! { #[serde(skip)] }
+            } else {
+                quote! {}
+            };
+            meaning.struct_output().borrow_mut().extend(quote! {
+                #skip_attr
+                pub __layer_cache: ::std::cell::RefCell<::std::collections::HashMap<::std::any::TypeId, *const ()>>,
+            });
+        }
+
         // Define the the instance `#ctor_init_name_id` method,
         // containing everything but `super()` and structure initialization.
         let statements = node.map(|node| node.statements.clone()).unwrap_or(vec![]);
@@ -42,6 +74,14 @@ impl ProcessingStep3_7 {
             let __cto1 = #initlayer2;
         }.try_into().unwrap());
 
+        // Cache a raw pointer to every ancestor layer right here, while we
+        // still have them as plain `Rc`s from allocating `__cto1`, so field
+        // accessors never have to re-walk the `#DATA_VARIANT_FIELD` chain on
+        // every single access (see `ProcessingStep3_2::define_accessors` for
+        // the read side that consults this cache). The `__layer_cache` field
+        // itself is declared on the root struct a few lines up, above.
+        m_new_out.extend::<TokenStream>(self.cache_layer_pointers(asc_meaning_list).try_into().unwrap());
+
         // If the meaning inherits another meaning:
         //
         // * At `M::new`, invoke `InheritedM::#ctor_init_name_id(&__cto1.0, ...super_arguments)`,
@@ -73,6 +113,78 @@ impl ProcessingStep3_7 {
                 #m_new_out
             }
         });
+
+        // With `#[smodel(serialize)]`, also emit a `from_data` constructor
+        // that re-hosts an already-populated `#DATA::M` tree (typically
+        // just loaded through `serde`) into a fresh arena, rebuilding the
+        // `Weak` self-root layering that `M::new` builds but that a
+        // deserialized `#DATA` tree never carried in the first place.
+        if serialize {
+            self.define_from_data(meaning, asc_meaning_list, &arena_type_name_id);
+        }
+    }
+
+    /// Emits, for every non-root layer in `asc_meaning_list` (i.e. every
+    /// ancestor meaning up to and including `meaning` itself), a statement
+    /// that records a raw pointer to that just-allocated layer into the
+    /// root struct's `__layer_cache`. The root (index `0`) never needs a
+    /// cache entry — it's already reached in O(1) through the weak
+    /// self-root — so the loop starts at `1`.
+    fn cache_layer_pointers(&self, asc_meaning_list: &[Symbol]) -> proc_macro2::TokenStream {
+        let root_accessor = proc_macro2::TokenStream::from_str("__cto1.0.upgrade().unwrap()").unwrap();
+        let mut out = proc_macro2::TokenStream::new();
+        for i in 1..asc_meaning_list.len() {
+            let layer_name = asc_meaning_list[i].name();
+            let layer_name_id = Ident::new(&layer_name, Span::call_site());
+            let data_id = Ident::new(DATA, Span::call_site());
+            let walk = proc_macro2::TokenStream::from_str(&self.match_layer(&asc_meaning_list[0..=i], 0, "__cto1.0.upgrade().unwrap()", false)).unwrap();
+            out.extend(quote! {
+                #root_accessor.__layer_cache.borrow_mut().insert(
+                    ::std::any::TypeId::of::<#data_id::#layer_name_id>(),
+                    (&*#walk as *const #data_id::#layer_name_id) as *const (),
+                );
+            });
+        }
+        out
+    }
+
+    /// Walks down to a `&#DATA::Mi` struct reference (rather than a field
+    /// within it), peeling one variant layer per entry of `asc_meaning_list`
+    /// after the first; a copy of `ProcessingStep3_2::match_layer`.
+    fn match_layer(&self, asc_meaning_list: &[Symbol], meaning_index: usize, base: &str, base_is_ref: bool) -> String {
+        let inherited = if asc_meaning_list.len() - meaning_index == 1 {
+            None
+        } else {
+            Some(asc_meaning_list[meaning_index].clone())
+        };
+        let meaning = asc_meaning_list[meaning_index + if inherited.is_some() { 1 } else { 0 }].clone();
+
+        let Some(inherited) = inherited else {
+            return if base_is_ref { format!("(&**{base})") } else { format!("(&*{base})") };
+        };
+        format!("(if let {DATA}::{}::{}(o) = &{base}.{DATA_VARIANT_FIELD} {{ {} }} else {{ panic!() }})",
+            DATA_VARIANT_PREFIX.to_owned() + &inherited.name(),
+            meaning.name(),
+            self.match_layer(asc_meaning_list, meaning_index + 1, "o", true))
+    }
+
+    fn define_from_data(&self, meaning: &Symbol, asc_meaning_list: &[Symbol], arena_type_name_id: &Ident) {
+        let root_meaning_name_id = Ident::new(&asc_meaning_list[0].name(), Span::call_site());
+        let data_id = Ident::new(DATA, Span::call_site());
+        let layers = proc_macro2::TokenStream::from_str(&Symbol::create_layers_over_weak_root("arena.allocate(data)", asc_meaning_list)).unwrap();
+        // A deserialized `#DATA` tree never went through `cache_layer_pointers`
+        // (it has no `M::new` call behind it), so without rebuilding the
+        // cache here every accessor on a `from_data`-hosted model would
+        // permanently fall back to the `match_layer` walk.
+        let cache_pointers = self.cache_layer_pointers(asc_meaning_list);
+
+        meaning.method_output().borrow_mut().extend(quote! {
+            pub fn from_data(arena: &#arena_type_name_id, data: #data_id::#root_meaning_name_id) -> Self {
+                let __cto1 = #layers;
+                #cache_pointers
+                __cto1
+            }
+        });
     }
 
     fn init_data(&self, asc_meaning_list: &[Symbol], meaning_index: usize) -> proc_macro2::TokenStream {